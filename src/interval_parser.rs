@@ -0,0 +1,120 @@
+//! Parser for human-friendly interval strings like `7d`, `12h30m`, or `2w`.
+//!
+//! Used by the scheduled-announcement feature so a user can type a recurring
+//! interval instead of a raw number of seconds.
+
+use anyhow::{anyhow, bail};
+use chrono::Duration;
+
+/// Number of seconds in each supported unit suffix.
+fn unit_seconds(unit: u8) -> Option<i64> {
+    match unit {
+        b's' => Some(1),
+        b'm' => Some(60),
+        b'h' => Some(3600),
+        b'd' => Some(86400),
+        b'w' => Some(604800),
+        _ => None,
+    }
+}
+
+/// Parse an interval string into a [`Duration`].
+///
+/// The input is a sequence of `<number><unit>` runs (whitespace between runs is
+/// ignored), where unit is one of `s`, `m`, `h`, `d`, `w`. Empty input, a bare
+/// number with no unit, or an unknown suffix is an error.
+pub fn parse(input: &str) -> anyhow::Result<Duration> {
+    let bytes = input.as_bytes();
+    let mut i = 0;
+    let mut total: i64 = 0;
+    let mut saw_run = false;
+
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == start {
+            bail!("unexpected character '{}' in interval", bytes[i] as char);
+        }
+
+        let number: i64 = input[start..i]
+            .parse()
+            .map_err(|_| anyhow!("number too large in interval"))?;
+
+        if i >= bytes.len() {
+            bail!("missing unit suffix after '{number}'");
+        }
+        let seconds = unit_seconds(bytes[i])
+            .ok_or_else(|| anyhow!("unknown unit suffix '{}'", bytes[i] as char))?;
+        i += 1;
+
+        let run = number
+            .checked_mul(seconds)
+            .ok_or_else(|| anyhow!("interval overflow"))?;
+        total = total
+            .checked_add(run)
+            .ok_or_else(|| anyhow!("interval overflow"))?;
+        saw_run = true;
+    }
+
+    if !saw_run {
+        bail!("empty interval");
+    }
+
+    // The real bound is chrono's `Duration` range, not i64-second overflow.
+    Duration::try_seconds(total).ok_or_else(|| anyhow!("interval overflow"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_unit() {
+        assert_eq!(parse("7d").unwrap(), Duration::days(7));
+        assert_eq!(parse("90m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse("2w").unwrap(), Duration::weeks(2));
+    }
+
+    #[test]
+    fn multiple_units() {
+        assert_eq!(
+            parse("12h30m").unwrap(),
+            Duration::hours(12) + Duration::minutes(30)
+        );
+        assert_eq!(
+            parse("1d2h3m4s").unwrap(),
+            Duration::days(1) + Duration::hours(2) + Duration::minutes(3) + Duration::seconds(4)
+        );
+    }
+
+    #[test]
+    fn whitespace_is_tolerated() {
+        assert_eq!(
+            parse("  1h 30m ").unwrap(),
+            Duration::hours(1) + Duration::minutes(30)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(parse("").is_err());
+        assert!(parse("   ").is_err());
+        assert!(parse("10").is_err());
+        assert!(parse("5x").is_err());
+    }
+
+    #[test]
+    fn rejects_overflow() {
+        // Exceeds i64 entirely: rejected while parsing the number.
+        assert!(parse("99999999999999999999w").is_err());
+        // Fits in i64 seconds but exceeds chrono's `Duration` range.
+        assert!(parse("99999999999w").is_err());
+    }
+}
@@ -1,34 +1,199 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use anyhow::anyhow;
 use anyhow::Context as _;
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 
 use poise::serenity_prelude as serenity;
 use poise::CreateReply;
 
-use shuttle_persist::PersistInstance;
+use serde::{Deserialize, Serialize};
+
 use shuttle_runtime::SecretStore;
 use shuttle_serenity::ShuttleSerenity;
 
+mod interval_parser;
+
+/// A persisted "days since" event.
+///
+/// Replaces the original `(String, DateTime<Utc>)` tuple so an event can also
+/// remember where to announce milestones and which ones it has already posted.
+#[derive(Clone, Serialize, Deserialize)]
+struct Event {
+    /// Text shown as "It has been x days since [text]".
+    text: String,
+    /// Baseline time the count is measured from.
+    time: DateTime<Utc>,
+    /// Channel to post scheduled announcements into, if configured.
+    channel: Option<serenity::ChannelId>,
+    /// Day-counts that should trigger an announcement (e.g. 30, 100, 365).
+    milestones: Vec<i64>,
+    /// Recurring announcement interval in seconds, if configured.
+    interval: Option<i64>,
+    /// Highest milestone already announced, to avoid duplicate posts.
+    last_announced: Option<i64>,
+    /// Index of the last recurring-interval period already announced, so a
+    /// period is never posted twice. A period that passes entirely between two
+    /// wake-ups is not posted.
+    last_interval_period: Option<i64>,
+    /// Past baselines, newest last, capped at [`Event::HISTORY_CAP`] entries.
+    history: Vec<DateTime<Utc>>,
+}
+
+/// An event as returned by an external JSON calendar endpoint.
+#[derive(Deserialize)]
+struct ExternalEvent {
+    title: String,
+    description: String,
+    start: DateTime<Utc>,
+}
+
+impl Event {
+    /// Maximum number of past baselines kept for streak statistics.
+    const HISTORY_CAP: usize = 100;
+
+    /// Create a fresh event starting at `time` with no announcement configuration.
+    fn new(text: String, time: DateTime<Utc>) -> Self {
+        Self {
+            text,
+            time,
+            channel: None,
+            milestones: Vec::new(),
+            interval: None,
+            last_announced: None,
+            last_interval_period: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// The longest streak ever recorded, as `(days, start)`, if any reset has
+    /// happened. Considers every gap between consecutive baselines.
+    fn longest_streak(&self, tz: Tz) -> Option<(i64, DateTime<Utc>)> {
+        let mut baselines = self.history.clone();
+        baselines.push(self.time);
+        baselines
+            .windows(2)
+            .map(|pair| {
+                let start = pair[0];
+                let days = (pair[1].with_timezone(&tz).date_naive()
+                    - start.with_timezone(&tz).date_naive())
+                .num_days();
+                (days, start)
+            })
+            .max_by_key(|(days, _)| *days)
+    }
+}
+
 struct Data {
-    persist: PersistInstance,
+    db: sled::Db,
 }
 
 type Error = anyhow::Error;
 type Context<'a> = poise::Context<'a, Data, Error>;
 
-async fn autocomplete_name<'a>(ctx: Context<'_>, partial: &str) -> Vec<String> {
-    if let (Ok(list), Some(guild)) = (ctx.data().persist.list(), ctx.guild_id()) {
-        let guild = guild.to_string();
-        list.iter()
-            .filter(|key| key.starts_with(&guild))
-            .map(|key| key.trim_start_matches(&format!("{guild}:")).to_string())
-            .filter(|name| name.contains(partial))
-            .collect()
+/// Theme color used for all embed responses.
+const THEME_COLOR: u32 = 0x5865F2;
+
+/// Maximum response body accepted by `import`, in bytes.
+const IMPORT_BODY_LIMIT: usize = 4 * 1024 * 1024;
+
+/// Maximum number of fields Discord allows in a single embed.
+const EMBED_FIELD_CAP: usize = 25;
+
+/// Shortest recurring interval allowed. The announcement loop wakes once a
+/// minute, so anything shorter can't be honoured and would post every wake-up.
+const MIN_INTERVAL_SECS: i64 = 60;
+
+/// Reject recurring intervals shorter than a single announcement-loop tick.
+fn validate_interval(seconds: i64) -> Result<i64, Error> {
+    if seconds < MIN_INTERVAL_SECS {
+        Err(anyhow!("*Interval must be at least {MIN_INTERVAL_SECS}s."))
     } else {
-        Vec::new()
+        Ok(seconds)
+    }
+}
+
+/// Ordering for the `list` command.
+#[derive(poise::ChoiceParameter)]
+enum SortOrder {
+    #[name = "by_name"]
+    ByName,
+    #[name = "by_days_desc"]
+    ByDaysDesc,
+    #[name = "by_days_asc"]
+    ByDaysAsc,
+}
+
+/// Load and decode an event, returning `None` when the key is absent.
+fn load_event(db: &sled::Db, key: &str) -> anyhow::Result<Option<Event>> {
+    match db.get(key)? {
+        Some(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// Atomically apply `f` to the event stored under `key`, retrying on contention.
+///
+/// Returns `false` when the key does not exist. The `compare_and_swap` loop
+/// means concurrent slash commands can't clobber each other's writes.
+fn modify_event<F>(db: &sled::Db, key: &str, mut f: F) -> anyhow::Result<bool>
+where
+    F: FnMut(&mut Event),
+{
+    loop {
+        let Some(old) = db.get(key)? else {
+            return Ok(false);
+        };
+        let mut event: Event = bincode::deserialize(&old)?;
+        f(&mut event);
+        let new = bincode::serialize(&event)?;
+        if db
+            .compare_and_swap(key, Some(old.as_ref()), Some(new))?
+            .is_ok()
+        {
+            return Ok(true);
+        }
     }
 }
 
+/// Look up the timezone configured for a guild, defaulting to UTC.
+fn guild_timezone(db: &sled::Db, guild: &str) -> Tz {
+    db.get(format!("tz:{guild}"))
+        .ok()
+        .flatten()
+        .and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+        .and_then(|name| name.parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+/// Count whole calendar days between `time` and now in the given timezone.
+///
+/// Unlike subtracting `DateTime`s this flips over at local midnight rather than
+/// every 24-hour span.
+fn calendar_days_since(tz: Tz, time: DateTime<Utc>) -> i64 {
+    let now = Utc::now().with_timezone(&tz).date_naive();
+    let then = time.with_timezone(&tz).date_naive();
+    (now - then).num_days()
+}
+
+async fn autocomplete_name<'a>(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let Some(guild) = ctx.guild_id() else {
+        return Vec::new();
+    };
+    let prefix = format!("{guild}:");
+    ctx.data()
+        .db
+        .scan_prefix(&prefix)
+        .keys()
+        .filter_map(|key| key.ok())
+        .filter_map(|key| String::from_utf8(key.to_vec()).ok())
+        .map(|key| key.trim_start_matches(&prefix).to_string())
+        .filter(|name| name.contains(partial))
+        .collect()
+}
+
 /// Create a new event.
 ///
 /// The [text] will display in the message as: "It has been x days since [text]"
@@ -37,15 +202,26 @@ async fn create(
     ctx: Context<'_>,
     #[description = "Name of the event."] name: String,
     #[description = "Text for the event (e.g. \"It has been x days since [text]\")"] text: String,
+    #[description = "Recurring announcement interval (e.g. \"7d\", \"12h30m\")."] interval: Option<
+        String,
+    >,
 ) -> Result<(), Error> {
     let guild = ctx.guild_id().context("*Invalid guild")?.to_string();
     let key = format!("{guild}:{name}");
 
-    if let Err(_) = ctx.data().persist.load::<String>(&key) {
-        ctx.data()
-            .persist
-            .save::<(String, DateTime<Utc>)>(&key, (text, Utc::now()))?;
+    let mut event = Event::new(text, Utc::now());
+    if let Some(interval) = interval {
+        event.interval = Some(validate_interval(interval_parser::parse(&interval)?.num_seconds())?);
+    }
+    let bytes = bincode::serialize(&event)?;
 
+    // Atomic create-if-absent: the swap only succeeds when no value exists yet.
+    if ctx
+        .data()
+        .db
+        .compare_and_swap(&key, None::<&[u8]>, Some(bytes))?
+        .is_ok()
+    {
         ctx.say("*Event created.").await?;
         Ok(())
     } else {
@@ -63,14 +239,27 @@ async fn update(
     #[autocomplete = "autocomplete_name"]
     name: String,
     #[description = "Text for the event (e.g. \"It has been x days since [text]\")"] text: String,
+    #[description = "Recurring announcement interval (e.g. \"7d\", \"12h30m\")."] interval: Option<
+        String,
+    >,
 ) -> Result<(), Error> {
     let guild = ctx.guild_id().context("Invalid guild")?.to_string();
     let key = format!("{guild}:{name}");
 
-    if let Ok((_, time)) = ctx.data().persist.load::<(String, DateTime<Utc>)>(&key) {
-        ctx.data()
-            .persist
-            .save::<(String, DateTime<Utc>)>(&key, (text, time))?;
+    let interval = interval
+        .map(|interval| {
+            interval_parser::parse(&interval).and_then(|d| validate_interval(d.num_seconds()))
+        })
+        .transpose()?;
+
+    let existed = modify_event(&ctx.data().db, &key, |event| {
+        event.text = text.clone();
+        if let Some(interval) = interval {
+            event.interval = Some(interval);
+        }
+    })?;
+
+    if existed {
         ctx.say("*Event updated.").await?;
         Ok(())
     } else {
@@ -78,6 +267,116 @@ async fn update(
     }
 }
 
+/// Configure automatic milestone announcements for an event.
+#[poise::command(slash_command)]
+async fn announce(
+    ctx: Context<'_>,
+    #[description = "Name of the event."]
+    #[autocomplete = "autocomplete_name"]
+    name: String,
+    #[description = "Channel to post announcements into."] channel: serenity::ChannelId,
+    #[description = "Comma-separated day-counts to announce at (e.g. \"30,100,365\")."]
+    milestones: Option<String>,
+    #[description = "Also announce every N seconds."] interval: Option<i64>,
+) -> Result<(), Error> {
+    let guild = ctx.guild_id().context("Invalid guild")?.to_string();
+    let key = format!("{guild}:{name}");
+
+    let interval = interval.map(validate_interval).transpose()?;
+
+    let milestones = milestones.map(|milestones| {
+        milestones
+            .split(',')
+            .filter_map(|part| part.trim().parse::<i64>().ok())
+            .collect::<Vec<_>>()
+    });
+
+    let existed = modify_event(&ctx.data().db, &key, |event| {
+        event.channel = Some(channel);
+        if let Some(milestones) = &milestones {
+            event.milestones = milestones.clone();
+        }
+        if let Some(interval) = interval {
+            event.interval = Some(interval);
+        }
+    })?;
+
+    if existed {
+        ctx.say("*Announcements configured.").await?;
+        Ok(())
+    } else {
+        Err(anyhow!("*Event does not exist."))
+    }
+}
+
+/// Import events from an external JSON calendar endpoint.
+///
+/// The endpoint must return a list of `{ title, description, start }` objects;
+/// each becomes a counter keyed by its title, skipping names that already exist.
+#[poise::command(slash_command)]
+async fn import(
+    ctx: Context<'_>,
+    #[description = "URL of a JSON endpoint returning a list of events."] url: String,
+) -> Result<(), Error> {
+    let guild = ctx.guild_id().context("Invalid guild")?.to_string();
+
+    // Bound both how long we wait and how much we read, so an admin-supplied
+    // URL that is slow or unbounded can't hang the handler or exhaust memory.
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+    let mut response = client.get(&url).send().await?.error_for_status()?;
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if body.len() > IMPORT_BODY_LIMIT {
+            return Err(anyhow!("*Response too large."));
+        }
+    }
+    let events: Vec<ExternalEvent> = serde_json::from_slice(&body)?;
+
+    let mut imported = 0;
+    for external in events {
+        let key = format!("{guild}:{}", external.title);
+        let event = Event::new(external.description, external.start);
+        let bytes = bincode::serialize(&event)?;
+
+        // Atomic create-if-absent, so existing names are left untouched.
+        if ctx
+            .data()
+            .db
+            .compare_and_swap(&key, None::<&[u8]>, Some(bytes))?
+            .is_ok()
+        {
+            imported += 1;
+        }
+    }
+
+    ctx.say(format!("*Imported {imported} events.")).await?;
+    Ok(())
+}
+
+/// Set the guild's timezone for counting calendar days.
+#[poise::command(slash_command)]
+async fn set_timezone(
+    ctx: Context<'_>,
+    #[description = "IANA timezone name (e.g. \"America/New_York\")."] timezone: String,
+) -> Result<(), Error> {
+    let guild = ctx.guild_id().context("Invalid guild")?.to_string();
+
+    // Validate against the IANA database before persisting.
+    timezone
+        .parse::<Tz>()
+        .map_err(|_| anyhow!("*Unknown timezone."))?;
+
+    ctx.data()
+        .db
+        .insert(format!("tz:{guild}"), timezone.as_bytes())?;
+    ctx.say("*Timezone set.").await?;
+    Ok(())
+}
+
 /// Show the number of days since the last event occurence.
 #[poise::command(slash_command)]
 async fn days_since(
@@ -89,15 +388,19 @@ async fn days_since(
     let guild = ctx.guild_id().context("Invalid guild")?.to_string();
     let key = format!("{guild}:{name}");
 
-    if let Ok((text, time)) = ctx.data().persist.load::<(String, DateTime<Utc>)>(&key) {
-        let days_since = (Utc::now() - time).num_days();
-        ctx.say(format!(
-            "It has been {} {} since {}.",
-            days_since,
-            if days_since == 1 { "day" } else { "days" },
-            text
-        ))
-        .await?;
+    if let Some(event) = load_event(&ctx.data().db, &key)? {
+        let tz = guild_timezone(&ctx.data().db, &guild);
+        let days_since = calendar_days_since(tz, event.time);
+        let embed = serenity::CreateEmbed::new()
+            .color(THEME_COLOR)
+            .title(name)
+            .description(format!(
+                "It has been {} {} since {}.",
+                days_since,
+                if days_since == 1 { "day" } else { "days" },
+                event.text
+            ));
+        ctx.send(CreateReply::default().embed(embed)).await?;
         Ok(())
     } else {
         Err(anyhow!("*Event does not exist."))
@@ -115,10 +418,22 @@ async fn reset(
     let guild = ctx.guild_id().context("Invalid_guild")?.to_string();
     let key = format!("{guild}:{name}");
 
-    if let Ok((text, _)) = ctx.data().persist.load::<(String, DateTime<Utc>)>(&key) {
-        ctx.data()
-            .persist
-            .save::<(String, DateTime<Utc>)>(&guild, (text.clone(), Utc::now()))?;
+    let mut text = String::new();
+    let existed = modify_event(&ctx.data().db, &key, |event| {
+        // Record the ending baseline before stamping a new one, capping the
+        // history to the most recent entries.
+        event.history.push(event.time);
+        if event.history.len() > Event::HISTORY_CAP {
+            let overflow = event.history.len() - Event::HISTORY_CAP;
+            event.history.drain(..overflow);
+        }
+        event.time = Utc::now();
+        event.last_announced = None;
+        event.last_interval_period = None;
+        text = event.text.clone();
+    })?;
+
+    if existed {
         ctx.say(format!("It has now been 0 days since {text}."))
             .await?;
         Ok(())
@@ -127,6 +442,45 @@ async fn reset(
     }
 }
 
+/// Report the current and record streaks for an event.
+#[poise::command(slash_command)]
+async fn record(
+    ctx: Context<'_>,
+    #[description = "Name of the event."]
+    #[autocomplete = "autocomplete_name"]
+    name: String,
+) -> Result<(), Error> {
+    let guild = ctx.guild_id().context("Invalid guild")?.to_string();
+    let key = format!("{guild}:{name}");
+
+    if let Some(event) = load_event(&ctx.data().db, &key)? {
+        let tz = guild_timezone(&ctx.data().db, &guild);
+        let current = calendar_days_since(tz, event.time);
+
+        let message = match event.longest_streak(tz) {
+            Some((days, start)) => format!(
+                "Current streak: {} {} since {}.\nLongest streak: {} {}, set on {}.",
+                current,
+                if current == 1 { "day" } else { "days" },
+                event.text,
+                days,
+                if days == 1 { "day" } else { "days" },
+                start.with_timezone(&tz).date_naive()
+            ),
+            None => format!(
+                "Current streak: {} {} since {}.\nNo record yet.",
+                current,
+                if current == 1 { "day" } else { "days" },
+                event.text
+            ),
+        };
+        ctx.say(message).await?;
+        Ok(())
+    } else {
+        Err(anyhow!("*Event does not exist."))
+    }
+}
+
 /// Remove an existing event.
 #[poise::command(slash_command)]
 async fn remove(
@@ -138,7 +492,7 @@ async fn remove(
     let guild = ctx.guild_id().context("Invalid_guild")?.to_string();
     let key = format!("{guild}:{name}");
 
-    if let Ok(_) = ctx.data().persist.remove(&key) {
+    if ctx.data().db.remove(&key)?.is_some() {
         ctx.say("*Event removed.").await?;
         Ok(())
     } else {
@@ -148,30 +502,67 @@ async fn remove(
 
 /// List all existing events.
 #[poise::command(slash_command)]
-async fn list(ctx: Context<'_>) -> Result<(), Error> {
+async fn list(
+    ctx: Context<'_>,
+    #[description = "Ordering of the events (defaults to longest-standing first)."] sort: Option<
+        SortOrder,
+    >,
+    #[description = "Also show each event's record."] stats: Option<bool>,
+) -> Result<(), Error> {
     let guild = ctx.guild_id().context("Invalid_guild")?.to_string();
+    let prefix = format!("{guild}:");
+    let stats = stats.unwrap_or(false);
+    let tz = guild_timezone(&ctx.data().db, &guild);
 
-    let mut list = String::new();
-    for item in ctx.data().persist.list()? {
-        if item.starts_with(&guild) {
-            let name = item.trim_start_matches(&format!("{guild}:"));
-            list.push_str(name);
-
-            let (text, _) = ctx.data().persist.load::<(String, DateTime<Utc>)>(&item)?;
-            list.push_str(": ");
-            list.push_str(&text);
+    // Collect each event with its current day count to drive the ordering.
+    let mut events = Vec::new();
+    for entry in ctx.data().db.scan_prefix(&prefix) {
+        let (key, value) = entry?;
+        let name = String::from_utf8(key.to_vec())?;
+        let name = name.trim_start_matches(&prefix).to_string();
+        let event: Event = bincode::deserialize(&value)?;
+        let days = calendar_days_since(tz, event.time);
+        events.push((name, event, days));
+    }
 
-            list.push('\n');
-        }
+    match sort.unwrap_or(SortOrder::ByDaysDesc) {
+        SortOrder::ByName => events.sort_by(|a, b| a.0.cmp(&b.0)),
+        SortOrder::ByDaysDesc => events.sort_by(|a, b| b.2.cmp(&a.2)),
+        SortOrder::ByDaysAsc => events.sort_by(|a, b| a.2.cmp(&b.2)),
     }
-    list.pop();
 
-    if list.is_empty() {
+    if events.is_empty() {
         ctx.say("*No events found").await?;
-    } else {
-        ctx.say(format!("*{list}")).await?;
+        return Ok(());
+    }
+
+    let mut embed = serenity::CreateEmbed::new()
+        .color(THEME_COLOR)
+        .title("Events");
+    // Discord caps an embed at 25 fields, so show the top entries and summarise
+    // the remainder in a footer rather than letting `send` reject the message.
+    let total = events.len();
+    let overflow = total.saturating_sub(EMBED_FIELD_CAP);
+    for (name, event, days) in events.into_iter().take(EMBED_FIELD_CAP) {
+        let mut value = format!(
+            "{} ({} {})",
+            event.text,
+            days,
+            if days == 1 { "day" } else { "days" }
+        );
+        if stats {
+            let record = event.longest_streak(tz).map_or(0, |(days, _)| days);
+            value.push_str(&format!(" — record {record}"));
+        }
+        embed = embed.field(name, value, false);
+    }
+    if overflow > 0 {
+        embed = embed.footer(serenity::CreateEmbedFooter::new(format!(
+            "…and {overflow} more"
+        )));
     }
 
+    ctx.send(CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
@@ -186,26 +577,119 @@ fn maybe_make_ephemeral(_: Context<'_>, create_reply: CreateReply) -> CreateRepl
     create_reply
 }
 
+/// Background loop that posts milestone announcements without manual commands.
+///
+/// Wakes every 60 seconds, walks every persisted event, and posts into the
+/// configured channel whenever the running day count newly reaches a milestone
+/// (or on the recurring interval). The last announced milestone is persisted so
+/// a post is never repeated across wake-ups.
+async fn announcement_loop(http: Arc<serenity::Http>, db: sled::Db) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        for entry in db.iter() {
+            let Ok((key, value)) = entry else {
+                continue;
+            };
+            let Ok(event) = bincode::deserialize::<Event>(&value) else {
+                // Non-event keys (e.g. per-guild timezones) don't decode here.
+                continue;
+            };
+            let Some(channel) = event.channel else {
+                continue;
+            };
+            let Ok(key_str) = String::from_utf8(key.to_vec()) else {
+                continue;
+            };
+            let Some((guild, _)) = key_str.split_once(':') else {
+                continue;
+            };
+
+            // Count calendar days in the guild timezone so milestones fire on
+            // the same day `days_since` reports, not on raw 24-hour spans.
+            let tz = guild_timezone(&db, guild);
+            let elapsed = Utc::now() - event.time;
+            let days = calendar_days_since(tz, event.time);
+
+            // Highest configured milestone that the count has reached.
+            let reached = event
+                .milestones
+                .iter()
+                .copied()
+                .filter(|milestone| days >= *milestone)
+                .max();
+
+            let crossed_milestone =
+                matches!(reached, Some(reached) if Some(reached) != event.last_announced);
+
+            // Which recurring period we're in, counting from the baseline. We
+            // post once when the period index advances, so drift can't
+            // double-post within a period; if the index advances by more than
+            // one between wake-ups, only a single post is made.
+            let period = event.interval.and_then(|interval| {
+                (interval >= MIN_INTERVAL_SECS).then(|| elapsed.num_seconds() / interval)
+            });
+            let on_interval = matches!(period, Some(period) if period >= 1 && Some(period) != event.last_interval_period);
+
+            if crossed_milestone || on_interval {
+                let message = format!(
+                    "It has been {} {} since {}.",
+                    days,
+                    if days == 1 { "day" } else { "days" },
+                    event.text
+                );
+                if channel.say(&http, message).await.is_ok() {
+                    // Route the update through the CAS loop so a reset or update
+                    // landing between our read and write isn't clobbered.
+                    let _ = modify_event(&db, &key_str, |event| {
+                        if let Some(reached) = reached {
+                            event.last_announced = Some(reached);
+                        }
+                        if on_interval {
+                            event.last_interval_period = period;
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
+
 #[shuttle_runtime::main]
 async fn main(
     #[shuttle_runtime::Secrets] secret_store: SecretStore,
-    #[shuttle_persist::Persist] persist: PersistInstance,
 ) -> ShuttleSerenity {
     // Get the discord token set in `Secrets.toml`
     let discord_token = secret_store
         .get("DISCORD_TOKEN")
         .context("'DISCORD_TOKEN' was not found")?;
 
+    let db = sled::open("dayssince.db").map_err(shuttle_runtime::CustomError::new)?;
+    // Keep a handle for the background announcement task before the rest is
+    // moved into the framework's data.
+    let announce_db = db.clone();
+
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![create(), update(), days_since(), reset(), remove(), list()],
+            commands: vec![
+                create(),
+                update(),
+                announce(),
+                import(),
+                set_timezone(),
+                days_since(),
+                reset(),
+                record(),
+                remove(),
+                list(),
+            ],
             reply_callback: Some(maybe_make_ephemeral),
             ..Default::default()
         })
         .setup(|ctx, _ready, framework| {
             Box::pin(async move {
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
-                Ok(Data { persist })
+                Ok(Data { db })
             })
         })
         .build();
@@ -216,5 +700,9 @@ async fn main(
             .await
             .map_err(shuttle_runtime::CustomError::new)?;
 
+    // Spawn the scheduled-announcement task with its own handles to the HTTP
+    // layer and the database so it can post without an incoming interaction.
+    tokio::spawn(announcement_loop(client.http.clone(), announce_db));
+
     Ok(client.into())
 }